@@ -0,0 +1,76 @@
+//! Requires the `async` feature: registers `Socket`'s fd with a `tokio`
+//! reactor and drives it as an `AsyncRead`.
+#![cfg(feature = "async")]
+
+use libc::{fcntl, F_GETFL, F_SETFL, O_NONBLOCK};
+
+use std::io;
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use tokio::io::unix::AsyncFd;
+use tokio::io::{AsyncRead, ReadBuf};
+
+use crate::socket::Socket;
+
+/// Sets `O_NONBLOCK` on the socket's fd so reads never block the calling thread.
+pub fn set_nonblocking(socket: &Socket) -> io::Result<()> {
+    let flags = unsafe { fcntl(socket.fd, F_GETFL) };
+    if flags < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    match unsafe { fcntl(socket.fd, F_SETFL, flags | O_NONBLOCK) } {
+        -1 => Err(io::Error::last_os_error()),
+        _ => Ok(()),
+    }
+}
+
+struct RawFdSource(RawFd);
+
+impl AsRawFd for RawFdSource {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0
+    }
+}
+
+/// A non-blocking `Socket` registered with a `tokio` reactor.
+///
+/// `io` is declared before `socket`: fields drop in declaration order, and
+/// `io` must deregister the fd from the reactor before `Socket::drop` closes
+/// it, or tokio's `epoll_ctl(DEL)` can race a recycled fd of the same number.
+pub struct AsyncSocket {
+    io: AsyncFd<RawFdSource>,
+    socket: Socket,
+}
+
+impl AsyncSocket {
+    pub fn new(socket: Socket) -> io::Result<AsyncSocket> {
+        set_nonblocking(&socket)?;
+        let io = AsyncFd::new(RawFdSource(socket.fd))?;
+        Ok(AsyncSocket { io, socket })
+    }
+}
+
+impl AsyncRead for AsyncSocket {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<io::Result<()>> {
+        let AsyncSocket { socket, io } = self.get_mut();
+        loop {
+            let mut guard = match io.poll_read_ready(cx) {
+                Poll::Ready(Ok(guard)) => guard,
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            };
+
+            let unfilled = buf.initialize_unfilled();
+            match guard.try_io(|_| io::Read::read(socket, unfilled)) {
+                Ok(Ok(n)) => {
+                    buf.advance(n);
+                    return Poll::Ready(Ok(()));
+                }
+                Ok(Err(e)) => return Poll::Ready(Err(e)),
+                Err(_would_block) => continue,
+            }
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use libc::{c_int, c_void, setsockopt, socklen_t, SOL_SOCKET};
+
+use std::io::{self, Error};
+use std::mem;
+
+use crate::socket::Socket;
+
+const SO_ATTACH_FILTER: c_int = 26;
+const SO_DETACH_FILTER: c_int = 27;
+
+/// One BPF instruction, matching `struct sock_filter` from `linux/filter.h`.
+/// Hand-assemble these or paste `tcpdump -dd` output.
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub struct SockFilter {
+    pub code: u16,
+    pub jt: u8,
+    pub jf: u8,
+    pub k: u32,
+}
+
+/// `struct sock_fprog`: the classic-BPF program handed to `SO_ATTACH_FILTER`.
+#[repr(C)]
+struct SockFprog {
+    len: u16,
+    filter: *const SockFilter,
+}
+
+impl Socket {
+    /// Installs a classic BPF program so the kernel filters packets before
+    /// they're copied to userspace.
+    pub fn attach_filter(&mut self, program: &[SockFilter]) -> io::Result<()> {
+        let prog = SockFprog { len: program.len() as u16, filter: program.as_ptr() };
+        match unsafe {
+            setsockopt(
+                self.fd,
+                SOL_SOCKET,
+                SO_ATTACH_FILTER,
+                &prog as *const _ as *const c_void,
+                mem::size_of::<SockFprog>() as socklen_t,
+            )
+        } {
+            0 => Ok(()),
+            _ => Err(Error::last_os_error()),
+        }
+    }
+
+    /// Removes a filter previously installed with [`Socket::attach_filter`].
+    pub fn detach_filter(&mut self) -> io::Result<()> {
+        let dummy: c_int = 0;
+        match unsafe {
+            setsockopt(
+                self.fd,
+                SOL_SOCKET,
+                SO_DETACH_FILTER,
+                &dummy as *const _ as *const c_void,
+                mem::size_of::<c_int>() as socklen_t,
+            )
+        } {
+            0 => Ok(()),
+            _ => Err(Error::last_os_error()),
+        }
+    }
+}
@@ -0,0 +1,15 @@
+extern crate libc;
+
+#[cfg(feature = "async")]
+pub mod async_io;
+pub mod filter;
+pub mod rx_ring;
+pub mod socket;
+pub mod tx_ring;
+
+#[cfg(feature = "async")]
+pub use async_io::AsyncSocket;
+pub use filter::SockFilter;
+pub use rx_ring::{RxRing, TpacketReq3};
+pub use socket::{InterfaceFlags, Socket};
+pub use tx_ring::TxRing;
@@ -0,0 +1,215 @@
+use libc::{c_int, c_void, mmap, munmap, poll, pollfd, MAP_FAILED, MAP_SHARED, POLLIN, PROT_READ, PROT_WRITE};
+
+use std::io::{self, Error, ErrorKind};
+use std::mem;
+use std::ptr;
+use std::slice;
+use std::sync::atomic::{fence, Ordering};
+
+use crate::socket::Socket;
+
+const TPACKET_V3: c_int = 2;
+
+const PACKET_VERSION: c_int = 10;
+const PACKET_RX_RING: c_int = 5;
+
+const TP_STATUS_KERNEL: u32 = 0;
+const TP_STATUS_USER: u32 = 1 << 0;
+
+/// Mirrors `struct tpacket_req3` from `linux/if_packet.h`, the argument to
+/// `setsockopt(PACKET_RX_RING)`/`setsockopt(PACKET_TX_RING)` under TPACKET_V3.
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub struct TpacketReq3 {
+    pub tp_block_size: u32,
+    pub tp_block_nr: u32,
+    pub tp_frame_size: u32,
+    pub tp_frame_nr: u32,
+    pub tp_retire_blk_tov: u32,
+    pub tp_sizeof_priv: u32,
+    pub tp_feature_req_word: u32,
+}
+
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+#[allow(dead_code)]
+struct TpacketBdTs {
+    ts_sec: u32,
+    ts_usec: u32,
+}
+
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+#[allow(dead_code)]
+struct TpacketHdrV1 {
+    block_status: u32,
+    num_pkts: u32,
+    offset_to_first_pkt: u32,
+    blk_len: u32,
+    seq_num: u64,
+    ts_first_pkt: TpacketBdTs,
+    ts_last_pkt: TpacketBdTs,
+}
+
+#[repr(C)]
+#[allow(dead_code)]
+struct TpacketBlockDesc {
+    version: u32,
+    offset_to_priv: u32,
+    hdr: TpacketHdrV1,
+}
+
+#[repr(C)]
+#[allow(dead_code)]
+struct Tpacket3Hdr {
+    tp_next_offset: u32,
+    tp_sec: u32,
+    tp_nsec: u32,
+    tp_snaplen: u32,
+    tp_len: u32,
+    tp_status: u32,
+    tp_mac: u16,
+    tp_net: u16,
+    tp_rxhash: u32,
+    tp_vlan_tci: u32,
+    tp_vlan_tpid: u16,
+    tp_padding: u16,
+}
+
+/// A memory-mapped `PACKET_RX_RING` (TPACKET_V3) for zero-copy capture.
+///
+/// Blocks are polled in order; once every frame in a block has been handed
+/// out, the block is released back to the kernel before moving on to the
+/// next one.
+pub struct RxRing {
+    socket: Socket,
+    map: *mut c_void,
+    map_len: usize,
+    req: TpacketReq3,
+    cur_block: usize,
+    pkts_remaining: u32,
+    next_pkt_offset: u32,
+}
+
+impl RxRing {
+    pub fn new(mut socket: Socket, req: TpacketReq3) -> io::Result<RxRing> {
+        socket.setsockopt(PACKET_VERSION, TPACKET_V3)?;
+        socket.setsockopt(PACKET_RX_RING, req)?;
+
+        let map_len = req.tp_block_size as usize * req.tp_block_nr as usize;
+        let map = unsafe {
+            mmap(ptr::null_mut(), map_len, PROT_READ | PROT_WRITE, MAP_SHARED, socket.fd, 0)
+        };
+        if map == MAP_FAILED {
+            return Err(Error::last_os_error());
+        }
+
+        Ok(RxRing { socket, map, map_len, req, cur_block: 0, pkts_remaining: 0, next_pkt_offset: 0 })
+    }
+
+    fn block_ptr(&self, index: usize) -> *mut u8 {
+        unsafe { (self.map as *mut u8).add(index * self.req.tp_block_size as usize) }
+    }
+
+    fn block_desc(&self, index: usize) -> &TpacketBlockDesc {
+        unsafe { &*(self.block_ptr(index) as *const TpacketBlockDesc) }
+    }
+
+    /// Reads `block_status`, the flag the kernel flips to `TP_STATUS_USER`
+    /// to hand a block to us. This word is shared, kernel-written memory, so
+    /// it's read with `read_volatile` (the compiler can't cache/reorder it
+    /// away) paired with an acquire fence (so nothing after this call can be
+    /// reordered ahead of observing ownership).
+    fn block_status(&self, index: usize) -> u32 {
+        let desc = self.block_ptr(index) as *mut TpacketBlockDesc;
+        let status = unsafe { ptr::read_volatile(ptr::addr_of!((*desc).hdr.block_status)) };
+        fence(Ordering::Acquire);
+        status
+    }
+
+    /// Inverse of [`RxRing::block_status`]: a release fence orders every
+    /// prior read of the block's frames before the volatile store that hands
+    /// the block back to the kernel.
+    fn set_block_status(&mut self, index: usize, status: u32) {
+        fence(Ordering::Release);
+        let desc = self.block_ptr(index) as *mut TpacketBlockDesc;
+        unsafe { ptr::write_volatile(ptr::addr_of_mut!((*desc).hdr.block_status), status) };
+    }
+
+    fn release_cur_block(&mut self) {
+        self.set_block_status(self.cur_block, TP_STATUS_KERNEL);
+        self.cur_block = (self.cur_block + 1) % self.req.tp_block_nr as usize;
+        self.next_pkt_offset = 0;
+    }
+
+    /// Returns the next captured frame as a slice into the mmap, or `None` if
+    /// no block is currently owned by userspace.
+    ///
+    /// This isn't `Iterator` because the returned slice borrows from `self`
+    /// for as long as the ring lives; callers poll this in a loop instead.
+    pub fn next_packet(&mut self) -> io::Result<Option<&[u8]>> {
+        if self.pkts_remaining == 0 {
+            if self.next_pkt_offset != 0 {
+                self.release_cur_block();
+            }
+
+            if self.block_status(self.cur_block) & TP_STATUS_USER == 0 {
+                return Ok(None);
+            }
+            let desc = self.block_desc(self.cur_block);
+            let num_pkts = desc.hdr.num_pkts;
+            let offset_to_first_pkt = desc.hdr.offset_to_first_pkt;
+
+            self.pkts_remaining = num_pkts;
+            self.next_pkt_offset = offset_to_first_pkt;
+
+            if self.pkts_remaining == 0 {
+                self.release_cur_block();
+                return Ok(None);
+            }
+        }
+
+        let block_size = self.req.tp_block_size as usize;
+        let offset = self.next_pkt_offset as usize;
+        if offset + mem::size_of::<Tpacket3Hdr>() > block_size {
+            return Err(Error::new(ErrorKind::Other, "frame header out of bounds"));
+        }
+
+        let block = self.block_ptr(self.cur_block);
+        let hdr = unsafe { &*(block.add(offset) as *const Tpacket3Hdr) };
+
+        let start = offset + hdr.tp_mac as usize;
+        let end = start + hdr.tp_snaplen as usize;
+        if start > block_size || end > block_size {
+            return Err(Error::new(ErrorKind::Other, "frame data out of bounds"));
+        }
+
+        let frame = unsafe { slice::from_raw_parts(block.add(start), end - start) };
+
+        self.pkts_remaining -= 1;
+        self.next_pkt_offset = if hdr.tp_next_offset != 0 { offset as u32 + hdr.tp_next_offset } else { offset as u32 };
+
+        Ok(Some(frame))
+    }
+
+    /// Blocks until the socket is readable (a block has been retired) or the
+    /// timeout (in milliseconds, `-1` for infinite) elapses.
+    pub fn poll(&self, timeout_ms: c_int) -> io::Result<bool> {
+        let mut fds = [pollfd { fd: self.socket.fd, events: POLLIN, revents: 0 }];
+        match unsafe { poll(fds.as_mut_ptr(), 1, timeout_ms) } {
+            -1 => Err(Error::last_os_error()),
+            _ => Ok(fds[0].revents & POLLIN != 0),
+        }
+    }
+
+    /// Raw fd, for integrating the ring into an external epoll/mio loop.
+    pub fn as_raw_fd(&self) -> c_int {
+        self.socket.fd
+    }
+}
+
+impl Drop for RxRing {
+    fn drop(&mut self) {
+        unsafe { munmap(self.map, self.map_len) };
+    }
+}
@@ -1,23 +1,71 @@
 extern crate libc;
 
 use libc::{
-    c_char, c_int, c_short, c_uint, c_ulong, c_void, getsockopt, if_nametoindex, ioctl, setsockopt,
-    socket, socklen_t, ETH_P_ALL, SOCK_RAW, SOL_PACKET,
+    bind, c_char, c_int, c_short, c_uint, c_ulong, c_void, close, getsockopt, if_nametoindex,
+    ioctl, recvfrom, sendto, setsockopt, socket, sockaddr, sockaddr_ll, socklen_t, ETH_P_ALL,
+    SOCK_RAW, SOL_PACKET,
 };
 pub use libc::{AF_PACKET, IFF_PROMISC, PF_PACKET};
 
 use std::ffi::CString;
 use std::io::{self, Error, ErrorKind};
 use std::mem;
+use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
+use std::ptr;
 
 const IFNAMESIZE: usize = 16;
 const IFREQUNIONSIZE: usize = 24;
 
 const SIOCGIFFLAGS: c_ulong = 35091; //0x00008913;
 const SIOCSIFFLAGS: c_ulong = 35092; //0x00008914;
+const SIOCGIFMTU: c_ulong = 35105; //0x00008921;
 
 pub const PACKET_FANOUT: c_int = 18;
 
+pub const PACKET_FANOUT_FLAG_ROLLOVER: u16 = 0x1000;
+pub const PACKET_FANOUT_FLAG_DEFRAG: u16 = 0x8000;
+
+/// Load-balancing strategy for a `PACKET_FANOUT` group, joined via
+/// [`Socket::join_fanout`].
+#[derive(Clone, Copy, Debug)]
+pub enum FanoutMode {
+    Hash,
+    Lb,
+    Cpu,
+    Rollover,
+    Rnd,
+    Qm,
+}
+
+impl FanoutMode {
+    fn as_u16(self) -> u16 {
+        match self {
+            FanoutMode::Hash => 0,
+            FanoutMode::Lb => 1,
+            FanoutMode::Cpu => 2,
+            FanoutMode::Rollover => 3,
+            FanoutMode::Rnd => 4,
+            FanoutMode::Qm => 5,
+        }
+    }
+}
+
+#[cfg(test)]
+mod fanout_tests {
+    use super::FanoutMode;
+
+    // Values from the PACKET_FANOUT_* constants in linux/if_packet.h.
+    #[test]
+    fn matches_kernel_constants() {
+        assert_eq!(FanoutMode::Hash.as_u16(), 0);
+        assert_eq!(FanoutMode::Lb.as_u16(), 1);
+        assert_eq!(FanoutMode::Cpu.as_u16(), 2);
+        assert_eq!(FanoutMode::Rollover.as_u16(), 3);
+        assert_eq!(FanoutMode::Rnd.as_u16(), 4);
+        assert_eq!(FanoutMode::Qm.as_u16(), 5);
+    }
+}
+
 #[derive(Clone, Debug)]
 #[repr(C)]
 struct IfReq {
@@ -49,6 +97,15 @@ impl IfReqUnion {
         union.data[1] = bytes[1];
         union
     }
+
+    fn as_int(&self) -> c_int {
+        c_int::from_be(
+            (self.data[0] as c_int) << 24
+                | (self.data[1] as c_int) << 16
+                | (self.data[2] as c_int) << 8
+                | (self.data[3] as c_int),
+        )
+    }
 }
 
 impl IfReq {
@@ -70,6 +127,10 @@ impl IfReq {
     fn ifr_flags(&self) -> c_short {
         self.union.as_short()
     }
+
+    fn ifr_mtu(&self) -> c_int {
+        self.union.as_int()
+    }
 }
 
 impl Default for IfReq {
@@ -78,7 +139,36 @@ impl Default for IfReq {
     }
 }
 
-#[derive(Clone, Debug)]
+/// Decoded `SIOCGIFFLAGS` bits, as returned by [`Socket::get_flags_parsed`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct InterfaceFlags {
+    pub up: bool,
+    pub broadcast: bool,
+    pub promisc: bool,
+    pub loopback: bool,
+    pub point_to_point: bool,
+    pub running: bool,
+    pub no_arp: bool,
+    pub multicast: bool,
+}
+
+impl InterfaceFlags {
+    fn from_raw(raw: c_short) -> InterfaceFlags {
+        let raw = raw as c_int;
+        InterfaceFlags {
+            up: raw & libc::IFF_UP != 0,
+            broadcast: raw & libc::IFF_BROADCAST != 0,
+            promisc: raw & IFF_PROMISC != 0,
+            loopback: raw & libc::IFF_LOOPBACK != 0,
+            point_to_point: raw & libc::IFF_POINTOPOINT != 0,
+            running: raw & libc::IFF_RUNNING != 0,
+            no_arp: raw & libc::IFF_NOARP != 0,
+            multicast: raw & libc::IFF_MULTICAST != 0,
+        }
+    }
+}
+
+#[derive(Debug)]
 pub struct Socket {
     ///File descriptor
     pub fd: c_int,
@@ -131,6 +221,26 @@ impl Socket {
         Ok(())
     }
 
+    /// Inverse of [`Socket::set_flag`]: read-modify-write the interface
+    /// flags with `flag` masked off, e.g. to leave promiscuous mode.
+    pub fn clear_flag(&mut self, flag: c_ulong) -> io::Result<()> {
+        let flags = self.get_flags()?.ifr_flags();
+        let new_flags = flags & !(flag as c_short);
+        let mut if_req = IfReq::with_if_name(&self.if_name)?;
+        if_req.union.data = IfReqUnion::from_short(new_flags).data;
+        self.ioctl(SIOCSIFFLAGS, if_req)?;
+        Ok(())
+    }
+
+    pub fn get_mtu(&self) -> io::Result<c_int> {
+        Ok(self.ioctl(SIOCGIFMTU, IfReq::with_if_name(&self.if_name)?)?.ifr_mtu())
+    }
+
+    /// Like [`Socket::set_flag`]'s raw `c_short`, but decoded into named bits.
+    pub fn get_flags_parsed(&self) -> io::Result<InterfaceFlags> {
+        Ok(InterfaceFlags::from_raw(self.get_flags()?.ifr_flags()))
+    }
+
     pub fn setsockopt<T>(&mut self, opt: c_int, opt_val: T) -> io::Result<()> {
         match unsafe {
             setsockopt(
@@ -149,6 +259,112 @@ impl Socket {
     pub fn getsockopt(&mut self, opt: c_int, opt_val: &*mut c_void) -> io::Result<()> {
         get_sock_opt(self.fd, opt, opt_val)
     }
+
+    /// Joins a `PACKET_FANOUT` group so the kernel spreads incoming packets
+    /// across every socket joined to `group_id`, e.g. one per worker thread.
+    pub fn join_fanout(&mut self, group_id: u16, mode: FanoutMode) -> io::Result<()> {
+        self.join_fanout_with_flags(group_id, mode, 0)
+    }
+
+    /// Like [`Socket::join_fanout`] but with extra flags such as
+    /// `PACKET_FANOUT_FLAG_ROLLOVER`/`PACKET_FANOUT_FLAG_DEFRAG` ORed in.
+    pub fn join_fanout_with_flags(&mut self, group_id: u16, mode: FanoutMode, flags: u16) -> io::Result<()> {
+        let value: c_int = ((mode.as_u16() | flags) as c_int) << 16 | group_id as c_int;
+        self.setsockopt(PACKET_FANOUT, value)
+    }
+
+    fn sockaddr_ll(&self) -> sockaddr_ll {
+        let mut addr: sockaddr_ll = unsafe { mem::zeroed() };
+        addr.sll_family = AF_PACKET as u16;
+        addr.sll_protocol = (ETH_P_ALL as u16).to_be();
+        addr.sll_ifindex = self.if_index as c_int;
+        addr
+    }
+
+    /// Binds the socket to `if_index`, restricting capture/injection to this interface.
+    pub fn bind(&mut self) -> io::Result<()> {
+        let addr = self.sockaddr_ll();
+        match unsafe {
+            bind(self.fd, &addr as *const sockaddr_ll as *const sockaddr, mem::size_of::<sockaddr_ll>() as socklen_t)
+        } {
+            0 => Ok(()),
+            _ => Err(Error::last_os_error()),
+        }
+    }
+
+    /// Injects a raw Ethernet frame on the bound interface.
+    pub fn send(&mut self, frame: &[u8]) -> io::Result<usize> {
+        let addr = self.sockaddr_ll();
+        let sent = unsafe {
+            sendto(
+                self.fd,
+                frame.as_ptr() as *const c_void,
+                frame.len(),
+                0,
+                &addr as *const sockaddr_ll as *const sockaddr,
+                mem::size_of::<sockaddr_ll>() as socklen_t,
+            )
+        };
+        match sent {
+            n if n < 0 => Err(Error::last_os_error()),
+            n => Ok(n as usize),
+        }
+    }
+}
+
+impl io::Read for Socket {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = unsafe {
+            recvfrom(self.fd, buf.as_mut_ptr() as *mut c_void, buf.len(), 0, ptr::null_mut(), ptr::null_mut())
+        };
+        match n {
+            n if n < 0 => Err(Error::last_os_error()),
+            n => Ok(n as usize),
+        }
+    }
+}
+
+impl io::Write for Socket {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = unsafe { sendto(self.fd, buf.as_ptr() as *const c_void, buf.len(), 0, ptr::null(), 0) };
+        match n {
+            n if n < 0 => Err(Error::last_os_error()),
+            n => Ok(n as usize),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for Socket {
+    fn drop(&mut self) {
+        // `IntoRawFd` sets `fd` to -1 to hand off ownership without closing it here.
+        if self.fd >= 0 {
+            unsafe { close(self.fd) };
+        }
+    }
+}
+
+impl AsRawFd for Socket {
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd
+    }
+}
+
+impl FromRawFd for Socket {
+    unsafe fn from_raw_fd(fd: RawFd) -> Socket {
+        Socket { fd, if_name: String::new(), if_index: 0, sock_type: 0 }
+    }
+}
+
+impl IntoRawFd for Socket {
+    fn into_raw_fd(mut self) -> RawFd {
+        let fd = self.fd;
+        self.fd = -1;
+        fd
+    }
 }
 
 pub fn get_sock_opt(fd: i32, opt: c_int, opt_val: &*mut c_void) -> io::Result<()> {
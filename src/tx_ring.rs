@@ -0,0 +1,155 @@
+use libc::{c_int, c_void, mmap, munmap, send, MAP_FAILED, MAP_SHARED, PROT_READ, PROT_WRITE};
+
+use std::io::{self, Error, ErrorKind};
+use std::mem;
+use std::ptr;
+use std::sync::atomic::{fence, Ordering};
+
+use crate::rx_ring::TpacketReq3;
+use crate::socket::Socket;
+
+const TPACKET_V2: c_int = 1;
+
+const PACKET_VERSION: c_int = 10;
+const PACKET_TX_RING: c_int = 13;
+
+const TP_STATUS_AVAILABLE: u32 = 0;
+const TP_STATUS_SEND_REQUEST: u32 = 1 << 0;
+
+/// Mirrors `struct tpacket_req`, the argument TPACKET_V1/V2 rings expect —
+/// unlike `TpacketReq3`, it has no V3-only block-retire tunables, so we
+/// can't just hand the kernel a `TpacketReq3` and rely on layout luck.
+#[repr(C)]
+struct TpacketReq {
+    tp_block_size: u32,
+    tp_block_nr: u32,
+    tp_frame_size: u32,
+    tp_frame_nr: u32,
+}
+
+impl From<TpacketReq3> for TpacketReq {
+    fn from(req: TpacketReq3) -> TpacketReq {
+        TpacketReq {
+            tp_block_size: req.tp_block_size,
+            tp_block_nr: req.tp_block_nr,
+            tp_frame_size: req.tp_frame_size,
+            tp_frame_nr: req.tp_frame_nr,
+        }
+    }
+}
+
+/// Mirrors `struct tpacket2_hdr`, the per-frame header used by `PACKET_TX_RING`.
+#[repr(C)]
+#[allow(dead_code)]
+struct Tpacket2Hdr {
+    tp_status: u32,
+    tp_len: u32,
+    tp_snaplen: u32,
+    tp_mac: u16,
+    tp_net: u16,
+    tp_sec: u32,
+    tp_nsec: u32,
+    tp_vlan_tci: u16,
+    tp_vlan_tpid: u16,
+    tp_padding: [u8; 4],
+}
+
+/// A memory-mapped `PACKET_TX_RING` (TPACKET_V2) for zero-copy injection.
+///
+/// Frames are queued with [`TxRing::send_frame`] and handed to the kernel in
+/// a batch with [`TxRing::flush`], mirroring [`crate::rx_ring::RxRing`]'s
+/// block-walking shape but addressed per-frame, since TX has no block-level
+/// retirement.
+pub struct TxRing {
+    socket: Socket,
+    map: *mut c_void,
+    map_len: usize,
+    req: TpacketReq3,
+    frames_per_block: usize,
+    cur_frame: usize,
+}
+
+impl TxRing {
+    pub fn new(mut socket: Socket, req: TpacketReq3) -> io::Result<TxRing> {
+        let frames_per_block = req.tp_block_size as usize / req.tp_frame_size as usize;
+        if frames_per_block * req.tp_frame_size as usize != req.tp_block_size as usize {
+            return Err(Error::new(ErrorKind::InvalidInput, "tp_block_size must be an exact multiple of tp_frame_size"));
+        }
+
+        socket.setsockopt(PACKET_VERSION, TPACKET_V2)?;
+        socket.setsockopt(PACKET_TX_RING, TpacketReq::from(req))?;
+        socket.bind()?;
+
+        let map_len = req.tp_block_size as usize * req.tp_block_nr as usize;
+        let map = unsafe {
+            mmap(ptr::null_mut(), map_len, PROT_READ | PROT_WRITE, MAP_SHARED, socket.fd, 0)
+        };
+        if map == MAP_FAILED {
+            return Err(Error::last_os_error());
+        }
+
+        Ok(TxRing { socket, map, map_len, req, frames_per_block, cur_frame: 0 })
+    }
+
+    fn frame_ptr(&self, index: usize) -> *mut u8 {
+        let block = index / self.frames_per_block;
+        let frame_in_block = index % self.frames_per_block;
+        let block_off = block * self.req.tp_block_size as usize;
+        let frame_off = frame_in_block * self.req.tp_frame_size as usize;
+        unsafe { (self.map as *mut u8).add(block_off + frame_off) }
+    }
+
+    /// Copies `frame` into the next free TX slot and marks it for sending.
+    /// Returns `WouldBlock` if the kernel hasn't drained that slot yet.
+    pub fn send_frame(&mut self, frame: &[u8]) -> io::Result<()> {
+        let data_off = mem::size_of::<Tpacket2Hdr>();
+        let frame_size = self.req.tp_frame_size as usize;
+        if data_off + frame.len() > frame_size {
+            return Err(Error::new(ErrorKind::InvalidInput, "frame larger than tp_frame_size"));
+        }
+
+        let ptr = self.frame_ptr(self.cur_frame);
+        let hdr = ptr as *mut Tpacket2Hdr;
+        // tp_status is kernel-shared memory the kernel flips asynchronously;
+        // read it volatile with an acquire fence, same as RxRing::block_status.
+        let status = unsafe { ptr::read_volatile(ptr::addr_of!((*hdr).tp_status)) };
+        fence(Ordering::Acquire);
+        if status != TP_STATUS_AVAILABLE {
+            return Err(Error::new(ErrorKind::WouldBlock, "TX slot not yet drained by kernel"));
+        }
+
+        unsafe {
+            ptr::copy_nonoverlapping(frame.as_ptr(), ptr.add(data_off), frame.len());
+            (*hdr).tp_len = frame.len() as u32;
+            (*hdr).tp_snaplen = frame.len() as u32;
+            (*hdr).tp_mac = data_off as u16;
+        }
+        // Release fence so the frame contents above are visible to the
+        // kernel before it observes TP_STATUS_SEND_REQUEST.
+        fence(Ordering::Release);
+        unsafe { ptr::write_volatile(ptr::addr_of_mut!((*hdr).tp_status), TP_STATUS_SEND_REQUEST) };
+
+        self.cur_frame = (self.cur_frame + 1) % self.req.tp_frame_nr as usize;
+        Ok(())
+    }
+
+    /// Kicks the kernel with a zero-length `send()`, which scans the ring
+    /// for every frame in `TP_STATUS_SEND_REQUEST` and transmits it.
+    pub fn flush(&mut self) -> io::Result<()> {
+        match unsafe { send(self.socket.fd, ptr::null(), 0, 0) } {
+            -1 => Err(Error::last_os_error()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Raw fd, for integrating the ring into an external epoll/mio loop.
+    pub fn as_raw_fd(&self) -> c_int {
+        self.socket.fd
+    }
+}
+
+impl Drop for TxRing {
+    fn drop(&mut self) {
+        unsafe { munmap(self.map, self.map_len) };
+    }
+}